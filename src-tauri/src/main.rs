@@ -3,11 +3,19 @@
     windows_subsystem = "windows"
 )]
 
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use tauri::{command, State};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Person {
@@ -45,6 +53,40 @@ pub struct AppSettings {
     theme: String, // "light" or "dark"
     auto_backup: bool,
     backup_count: u32,
+    retention: RetentionPolicy,
+    encryption: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct RetentionPolicy {
+    keep_last: u32,
+    keep_daily: u32,
+    keep_weekly: u32,
+    keep_monthly: u32,
+    keep_yearly: u32,
+}
+
+impl RetentionPolicy {
+    /// True if at least one bucket would actually retain a backup.
+    fn keeps_something(&self) -> bool {
+        self.keep_last > 0
+            || self.keep_daily > 0
+            || self.keep_weekly > 0
+            || self.keep_monthly > 0
+            || self.keep_yearly > 0
+    }
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy {
+            keep_last: 10,
+            keep_daily: 0,
+            keep_weekly: 0,
+            keep_monthly: 0,
+            keep_yearly: 0,
+        }
+    }
 }
 
 impl Default for AppData {
@@ -56,26 +98,136 @@ impl Default for AppData {
                 theme: "light".to_string(),
                 auto_backup: true,
                 backup_count: 10,
+                retention: RetentionPolicy::default(),
+                encryption: false,
             },
             version: "1.0.0".to_string(),
         }
     }
 }
 
+// On-disk header: magic marker, format version, Argon2 salt, AEAD nonce, then ciphertext.
+const ENCRYPTION_MAGIC: &[u8; 4] = b"FRDB";
+const ENCRYPTION_VERSION: u8 = 1;
+const ENCRYPTION_SALT_LEN: usize = 16;
+const ENCRYPTION_NONCE_LEN: usize = 24;
+const ENCRYPTION_KEY_LEN: usize = 32;
+const ENCRYPTION_HEADER_LEN: usize =
+    ENCRYPTION_MAGIC.len() + 1 + ENCRYPTION_SALT_LEN + ENCRYPTION_NONCE_LEN;
+
+fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.len() >= ENCRYPTION_HEADER_LEN && &bytes[..ENCRYPTION_MAGIC.len()] == ENCRYPTION_MAGIC
+}
+
+fn derive_encryption_key(passphrase: &str, salt: &[u8]) -> Result<[u8; ENCRYPTION_KEY_LEN], String> {
+    let mut key = [0u8; ENCRYPTION_KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive encryption key: {}", e))?;
+    Ok(key)
+}
+
+// Salt recorded in an encrypted file/chunk's header.
+fn encrypted_salt(bytes: &[u8]) -> Option<&[u8]> {
+    if !is_encrypted(bytes) {
+        return None;
+    }
+    let salt_start = ENCRYPTION_MAGIC.len() + 1;
+    Some(&bytes[salt_start..salt_start + ENCRYPTION_SALT_LEN])
+}
+
+// Seals `plaintext` with an already-derived `key`, recording `salt` for `encrypted_salt` to recover later.
+fn seal_with_key(plaintext: &[u8], salt: &[u8; ENCRYPTION_SALT_LEN], key: &[u8; ENCRYPTION_KEY_LEN]) -> Result<Vec<u8>, String> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let mut nonce_bytes = [0u8; ENCRYPTION_NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|_| "Failed to encrypt data".to_string())?;
+
+    let mut out = Vec::with_capacity(ENCRYPTION_HEADER_LEN + ciphertext.len());
+    out.extend_from_slice(ENCRYPTION_MAGIC);
+    out.push(ENCRYPTION_VERSION);
+    out.extend_from_slice(salt);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+// Opens `bytes` with an already-derived `key` (from the salt `encrypted_salt` reports for it).
+fn open_with_key(bytes: &[u8], key: &[u8; ENCRYPTION_KEY_LEN]) -> Result<Vec<u8>, String> {
+    if !is_encrypted(bytes) {
+        return Err("File is not a recognized encrypted data file".to_string());
+    }
+
+    let version = bytes[ENCRYPTION_MAGIC.len()];
+    if version != ENCRYPTION_VERSION {
+        return Err(format!("Unsupported encrypted file version: {}", version));
+    }
+
+    let salt_start = ENCRYPTION_MAGIC.len() + 1;
+    let nonce_start = salt_start + ENCRYPTION_SALT_LEN;
+    let ciphertext_start = nonce_start + ENCRYPTION_NONCE_LEN;
+
+    let nonce = XNonce::from_slice(&bytes[nonce_start..ciphertext_start]);
+    let ciphertext = &bytes[ciphertext_start..];
+
+    let cipher = XChaCha20Poly1305::new(key.into());
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| "Wrong passphrase or corrupted file".to_string())
+}
+
+fn encrypt_bytes(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; ENCRYPTION_SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_encryption_key(passphrase, &salt)?;
+    seal_with_key(plaintext, &salt, &key)
+}
+
+fn decrypt_bytes(bytes: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    let salt = encrypted_salt(bytes).ok_or_else(|| "File is not a recognized encrypted data file".to_string())?;
+    let key = derive_encryption_key(passphrase, salt)?;
+    open_with_key(bytes, &key)
+}
+
+// Looks up a previously-derived key for `salt` in `cache`, deriving and caching it on first use.
+fn cached_decryption_key(
+    cache: &mut HashMap<Vec<u8>, [u8; ENCRYPTION_KEY_LEN]>,
+    salt: &[u8],
+    passphrase: &str,
+) -> Result<[u8; ENCRYPTION_KEY_LEN], String> {
+    if let Some(key) = cache.get(salt) {
+        return Ok(*key);
+    }
+    let key = derive_encryption_key(passphrase, salt)?;
+    cache.insert(salt.to_vec(), key);
+    Ok(key)
+}
+
 #[command]
-fn load_data_file(path: String) -> Result<AppData, String> {
+fn load_data_file(path: String, passphrase: Option<String>) -> Result<AppData, String> {
     if !Path::new(&path).exists() {
         return Ok(AppData::default());
     }
-    
-    match fs::read_to_string(&path) {
-        Ok(content) => {
-            // Handle both AppData format and simple CSV text for export
-            if content.starts_with('{') {
-                match serde_json::from_str::<AppData>(&content) {
-                    Ok(data) => Ok(data),
-                    Err(e) => Err(format!("Failed to parse JSON: {}", e)),
-                }
+
+    match fs::read(&path) {
+        Ok(bytes) => {
+            if is_encrypted(&bytes) {
+                let passphrase = passphrase
+                    .ok_or_else(|| "This file is encrypted; a passphrase is required".to_string())?;
+                let plaintext = decrypt_bytes(&bytes, &passphrase)?;
+                let content = String::from_utf8(plaintext)
+                    .map_err(|e| format!("Decrypted content is not valid UTF-8: {}", e))?;
+                serde_json::from_str::<AppData>(&content)
+                    .map_err(|e| format!("Failed to parse JSON: {}", e))
+            } else if bytes.starts_with(b"{") {
+                // Handle both AppData format and simple CSV text for export
+                let content = String::from_utf8_lossy(&bytes);
+                serde_json::from_str::<AppData>(&content)
+                    .map_err(|e| format!("Failed to parse JSON: {}", e))
             } else {
                 // Return error for non-JSON files
                 Err("File is not a valid JSON data file".to_string())
@@ -86,7 +238,12 @@ fn load_data_file(path: String) -> Result<AppData, String> {
 }
 
 #[command]
-fn save_data_file(path: String, data: serde_json::Value) -> Result<(), String> {
+fn save_data_file(
+    path: String,
+    data: serde_json::Value,
+    encryption: bool,
+    passphrase: Option<String>,
+) -> Result<(), String> {
     // Check if data is a string (CSV export) or AppData object
     let content = if data.is_string() {
         data.as_str().unwrap().to_string()
@@ -97,35 +254,330 @@ fn save_data_file(path: String, data: serde_json::Value) -> Result<(), String> {
             Err(e) => return Err(format!("Failed to serialize data: {}", e)),
         }
     };
-    
-    match fs::write(&path, content) {
+
+    let bytes = if encryption {
+        let passphrase = passphrase
+            .ok_or_else(|| "Encryption is enabled but no passphrase was supplied".to_string())?;
+        encrypt_bytes(content.as_bytes(), &passphrase)?
+    } else {
+        content.into_bytes()
+    };
+
+    match fs::write(&path, bytes) {
         Ok(_) => Ok(()),
         Err(e) => Err(format!("Failed to write file: {}", e)),
     }
 }
 
+// Content-defined chunking bounds; `CHUNK_AVG_SIZE` must be a power of two (used as a mask).
+const CHUNK_MIN_SIZE: usize = 16 * 1024;
+const CHUNK_AVG_SIZE: usize = 64 * 1024;
+const CHUNK_MAX_SIZE: usize = 256 * 1024;
+const CHUNK_MASK: u64 = (CHUNK_AVG_SIZE as u64) - 1;
+
+// Gear-hash table of per-byte constants, generated at compile time with splitmix64.
+const GEAR_TABLE: [u64; 256] = build_gear_table();
+
+const fn build_gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut seed: u64 = 0x9E3779B97F4A7C15;
+    let mut i = 0;
+    while i < 256 {
+        seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        table[i] = z;
+        i += 1;
+    }
+    table
+}
+
+// Splits `data` on content-defined boundaries so local edits only shift nearby chunks.
+fn content_defined_chunks(data: &[u8]) -> Vec<&[u8]> {
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u64 = 0;
+
+    for (i, &byte) in data.iter().enumerate() {
+        hash = (hash << 1).wrapping_add(GEAR_TABLE[byte as usize]);
+        let len = i - start + 1;
+        if (len >= CHUNK_MIN_SIZE && hash & CHUNK_MASK == 0) || len >= CHUNK_MAX_SIZE {
+            chunks.push(&data[start..=i]);
+            start = i + 1;
+            hash = 0;
+        }
+    }
+    if start < data.len() {
+        chunks.push(&data[start..]);
+    }
+    chunks
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hash_chunk(chunk: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(chunk);
+    hex_encode(&hasher.finalize())
+}
+
+fn chunk_store_path(directory: &Path) -> PathBuf {
+    directory.join("chunks")
+}
+
+fn chunk_path(chunks_dir: &Path, hash: &str) -> PathBuf {
+    chunks_dir.join(format!("{}.chunk", hash))
+}
+
+// A backup "file" is a manifest: ordered chunk hashes plus metadata, not the data itself.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupManifest {
+    created: String, // RFC3339
+    size: u64,       // serialized plaintext size in bytes
+    chunks: Vec<String>,
+    checksum: String, // SHA-256 over the exact bytes stored for each chunk, in order
+}
+
+/// Re-hashes a manifest's on-disk chunk bytes; `None` if any chunk is missing.
+fn compute_stored_checksum(manifest: &BackupManifest, chunks_dir: &Path) -> Option<String> {
+    let mut hasher = Sha256::new();
+    for hash in &manifest.chunks {
+        let bytes = fs::read(chunk_path(chunks_dir, hash)).ok()?;
+        hasher.update(&bytes);
+    }
+    Some(hex_encode(&hasher.finalize()))
+}
+
+fn backup_file_stem(file_name: &str) -> Option<String> {
+    file_name.find("_backup_").map(|idx| file_name[..idx].to_string())
+}
+
+fn read_manifest(path: &Path) -> Option<BackupManifest> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// Every chunk hash referenced by any surviving manifest for `file_stem` in `directory`.
+fn referenced_chunk_hashes(directory: &Path, file_stem: &str) -> HashSet<String> {
+    let mut referenced = HashSet::new();
+    if let Ok(entries) = fs::read_dir(directory) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with(&format!("{}_backup_", file_stem)) && file_name.ends_with(".json") {
+                if let Some(manifest) = read_manifest(&entry.path()) {
+                    referenced.extend(manifest.chunks);
+                }
+            }
+        }
+    }
+    referenced
+}
+
+fn garbage_collect_chunks(directory: &Path, file_stem: &str) {
+    let chunks_dir = chunk_store_path(directory);
+    if !chunks_dir.exists() {
+        return;
+    }
+
+    let referenced = referenced_chunk_hashes(directory, file_stem);
+    if let Ok(entries) = fs::read_dir(&chunks_dir) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if let Some(hash) = file_name.strip_suffix(".chunk") {
+                if !referenced.contains(hash) {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
+}
+
+// One row per snapshot, appended by `create_backup`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GenerationRecord {
+    generation: u64,
+    manifest: String, // backup manifest file name, relative to `directory`
+    created: String,  // RFC3339, when the snapshot was started
+    finished: String, // RFC3339, when the snapshot finished writing
+    size: u64,
+    household_count: usize,
+    total_auc: f64,
+    app_version: String,
+}
+
+fn generation_index_path(directory: &Path, file_stem: &str) -> PathBuf {
+    directory.join(format!("{}_generations.jsonl", file_stem))
+}
+
+fn read_generation_index(path: &Path) -> Vec<GenerationRecord> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn rewrite_generation_index(path: &Path, records: &[GenerationRecord]) -> Result<(), String> {
+    let mut content = String::new();
+    for record in records {
+        let line = serde_json::to_string(record)
+            .map_err(|e| format!("Failed to serialize generation record: {}", e))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to update generation index: {}", e))
+}
+
+fn append_generation_record(path: &Path, record: &GenerationRecord) -> Result<(), String> {
+    let line = serde_json::to_string(record)
+        .map_err(|e| format!("Failed to serialize generation record: {}", e))?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open generation index: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to append generation record: {}", e))
+}
+
+fn next_generation_id(records: &[GenerationRecord]) -> u64 {
+    records.iter().map(|r| r.generation).max().map_or(1, |max| max + 1)
+}
+
+// Drops any generation row whose manifest file no longer exists.
+fn prune_generation_index(directory: &Path, file_stem: &str) {
+    let index_path = generation_index_path(directory, file_stem);
+    let records = read_generation_index(&index_path);
+    let retained: Vec<GenerationRecord> = records
+        .into_iter()
+        .filter(|record| directory.join(&record.manifest).exists())
+        .collect();
+    let _ = rewrite_generation_index(&index_path, &retained);
+}
+
 #[command]
-fn create_backup(path: String, data: AppData) -> Result<String, String> {
+fn create_backup(
+    path: String,
+    data: AppData,
+    encryption: bool,
+    passphrase: Option<String>,
+) -> Result<String, String> {
     let file_path = Path::new(&path);
     let file_stem = file_path.file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("backup");
     let parent_dir = file_path.parent().unwrap_or(Path::new("."));
-    
+    let chunks_dir = chunk_store_path(parent_dir);
+    fs::create_dir_all(&chunks_dir).map_err(|e| format!("Failed to create chunk store: {}", e))?;
+
     let now: DateTime<Utc> = Utc::now();
     let timestamp = now.format("%Y-%m-%d_%H-%M-%S");
     let backup_name = format!("{}_backup_{}.json", file_stem, timestamp);
-    let backup_path = parent_dir.join(backup_name);
-    
-    match serde_json::to_string_pretty(&data) {
-        Ok(json) => {
-            match fs::write(&backup_path, json) {
-                Ok(_) => Ok(backup_path.to_string_lossy().to_string()),
-                Err(e) => Err(format!("Failed to create backup: {}", e)),
+    let backup_path = parent_dir.join(&backup_name);
+
+    let json = serde_json::to_string_pretty(&data)
+        .map_err(|e| format!("Failed to serialize backup data: {}", e))?;
+    let plaintext = json.into_bytes();
+
+    // Derived once, when the first chunk is processed, and reused to seal
+    // every new chunk in this backup, rather than re-running the KDF per chunk.
+    let mut backup_key: Option<([u8; ENCRYPTION_SALT_LEN], [u8; ENCRYPTION_KEY_LEN])> = None;
+
+    let mut chunk_hashes = Vec::new();
+    let mut checksum_hasher = Sha256::new();
+    for chunk in content_defined_chunks(&plaintext) {
+        let plain_hash = hash_chunk(chunk);
+        let mut chunk_id = plain_hash.clone();
+        let stored_path = chunk_path(&chunks_dir, &plain_hash);
+
+        let stored_bytes = if !encryption {
+            if stored_path.exists() {
+                fs::read(&stored_path).map_err(|e| format!("Failed to read existing chunk: {}", e))?
+            } else {
+                fs::write(&stored_path, chunk).map_err(|e| format!("Failed to write chunk: {}", e))?;
+                chunk.to_vec()
             }
-        }
-        Err(e) => Err(format!("Failed to serialize backup data: {}", e)),
+        } else {
+            let passphrase = passphrase.clone().ok_or_else(|| {
+                "Encryption is enabled but no passphrase was supplied".to_string()
+            })?;
+            let (salt, key) = match &backup_key {
+                Some(pair) => *pair,
+                None => {
+                    let mut salt = [0u8; ENCRYPTION_SALT_LEN];
+                    OsRng.fill_bytes(&mut salt);
+                    let key = derive_encryption_key(&passphrase, &salt)?;
+                    backup_key = Some((salt, key));
+                    (salt, key)
+                }
+            };
+
+            let existing = if stored_path.exists() {
+                Some(fs::read(&stored_path).map_err(|e| format!("Failed to read existing chunk: {}", e))?)
+            } else {
+                None
+            };
+            // Reuse the existing chunk only if it was already sealed under the
+            // current passphrase; otherwise reseal under a salt-qualified id so
+            // older manifests still referencing the original file are unaffected.
+            let reusable = existing.as_ref().is_some_and(|bytes| {
+                encrypted_salt(bytes)
+                    .and_then(|salt| derive_encryption_key(&passphrase, salt).ok())
+                    .map(|key| open_with_key(bytes, &key).is_ok())
+                    .unwrap_or(false)
+            });
+
+            let had_existing = existing.is_some();
+            match existing {
+                Some(bytes) if reusable => bytes,
+                _ => {
+                    let bytes = seal_with_key(chunk, &salt, &key)?;
+                    if had_existing {
+                        chunk_id = format!("{}-{}", plain_hash, hex_encode(&salt));
+                    }
+                    let resealed_path = chunk_path(&chunks_dir, &chunk_id);
+                    fs::write(&resealed_path, &bytes).map_err(|e| format!("Failed to write chunk: {}", e))?;
+                    bytes
+                }
+            }
+        };
+
+        checksum_hasher.update(&stored_bytes);
+        chunk_hashes.push(chunk_id);
     }
+
+    let manifest = BackupManifest {
+        created: now.to_rfc3339(),
+        size: plaintext.len() as u64,
+        chunks: chunk_hashes,
+        checksum: hex_encode(&checksum_hasher.finalize()),
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+
+    fs::write(&backup_path, manifest_json).map_err(|e| format!("Failed to create backup: {}", e))?;
+
+    let index_path = generation_index_path(parent_dir, file_stem);
+    let generation = next_generation_id(&read_generation_index(&index_path));
+    let record = GenerationRecord {
+        generation,
+        manifest: backup_name,
+        created: now.to_rfc3339(),
+        finished: Utc::now().to_rfc3339(),
+        size: plaintext.len() as u64,
+        household_count: data.households.len(),
+        total_auc: data.households.iter().map(|h| h.auc).sum(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    append_generation_record(&index_path, &record)?;
+
+    Ok(backup_path.to_string_lossy().to_string())
 }
 
 #[command]
@@ -160,6 +612,108 @@ fn cleanup_old_backups(directory: String, file_stem: String, keep_count: u32) ->
         let _ = fs::remove_file(path);
     }
 
+    garbage_collect_chunks(dir_path, &file_stem);
+    prune_generation_index(dir_path, &file_stem);
+
+    Ok(())
+}
+
+// Parses the `%Y-%m-%d_%H-%M-%S` timestamp out of a `{file_stem}_backup_*.json`
+// file name, as written by `create_backup`.
+fn parse_backup_timestamp(file_name: &str, file_stem: &str) -> Option<NaiveDateTime> {
+    let prefix = format!("{}_backup_", file_stem);
+    let timestamp = file_name.strip_prefix(&prefix)?.strip_suffix(".json")?;
+    NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d_%H-%M-%S").ok()
+}
+
+fn daily_key(ts: &NaiveDateTime) -> String {
+    ts.format("%Y-%m-%d").to_string()
+}
+
+fn weekly_key(ts: &NaiveDateTime) -> String {
+    let iso_week = ts.iso_week();
+    format!("{}-{:02}", iso_week.year(), iso_week.week())
+}
+
+fn monthly_key(ts: &NaiveDateTime) -> String {
+    ts.format("%Y-%m").to_string()
+}
+
+fn yearly_key(ts: &NaiveDateTime) -> String {
+    ts.format("%Y").to_string()
+}
+
+// Marks the first `count` backups (newest-first) that introduce a new period
+// key as "keep", matching `keep[i]` by index into `backups`.
+fn mark_bucket(
+    backups: &[(PathBuf, NaiveDateTime)],
+    keep: &mut [bool],
+    count: u32,
+    period_key: fn(&NaiveDateTime) -> String,
+) {
+    if count == 0 {
+        return;
+    }
+
+    let mut seen = HashSet::new();
+    let mut kept = 0u32;
+    for (i, (_, timestamp)) in backups.iter().enumerate() {
+        if kept >= count {
+            break;
+        }
+        if seen.insert(period_key(timestamp)) {
+            keep[i] = true;
+            kept += 1;
+        }
+    }
+}
+
+#[command]
+fn prune_backups(directory: String, file_stem: String, policy: RetentionPolicy) -> Result<(), String> {
+    // A misconfigured (all-zero) policy must never wipe out the backup directory.
+    if !policy.keeps_something() {
+        return Ok(());
+    }
+
+    let dir_path = Path::new(&directory);
+    if !dir_path.exists() {
+        return Ok(());
+    }
+
+    let mut backups = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(dir_path) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if let Some(timestamp) = parse_backup_timestamp(&file_name, &file_stem) {
+                backups.push((entry.path(), timestamp));
+            }
+        }
+    }
+
+    // Sort by parsed timestamp, newest first.
+    backups.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut keep = vec![false; backups.len()];
+
+    for i in 0..(policy.keep_last as usize).min(backups.len()) {
+        keep[i] = true;
+    }
+
+    mark_bucket(&backups, &mut keep, policy.keep_daily, daily_key);
+    mark_bucket(&backups, &mut keep, policy.keep_weekly, weekly_key);
+    mark_bucket(&backups, &mut keep, policy.keep_monthly, monthly_key);
+    mark_bucket(&backups, &mut keep, policy.keep_yearly, yearly_key);
+
+    for (i, (path, _)) in backups.iter().enumerate() {
+        if !keep[i] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    garbage_collect_chunks(dir_path, &file_stem);
+    prune_generation_index(dir_path, &file_stem);
+
     Ok(())
 }
 
@@ -236,24 +790,23 @@ fn export_backup_list(directory: String, file_stem: String) -> Result<Vec<Backup
         return Ok(Vec::new());
     }
 
+    let chunks_dir = chunk_store_path(dir_path);
     let mut backups = Vec::new();
-    
+
     if let Ok(entries) = fs::read_dir(dir_path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let file_name = entry.file_name().to_string_lossy().to_string();
-                if file_name.starts_with(&format!("{}_backup_", file_stem)) && file_name.ends_with(".json") {
-                    if let Ok(metadata) = entry.metadata() {
-                        if let Ok(created) = metadata.created() {
-                            let created_datetime: DateTime<Utc> = created.into();
-                            backups.push(BackupInfo {
-                                filename: file_name,
-                                path: entry.path().to_string_lossy().to_string(),
-                                created: created_datetime.to_rfc3339(),
-                                size: metadata.len(),
-                            });
-                        }
-                    }
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with(&format!("{}_backup_", file_stem)) && file_name.ends_with(".json") {
+                if let Some(manifest) = read_manifest(&entry.path()) {
+                    let verified = compute_stored_checksum(&manifest, &chunks_dir)
+                        .map(|checksum| checksum == manifest.checksum);
+                    backups.push(BackupInfo {
+                        filename: file_name,
+                        path: entry.path().to_string_lossy().to_string(),
+                        created: manifest.created,
+                        size: manifest.size,
+                        verified,
+                    });
                 }
             }
         }
@@ -271,32 +824,241 @@ pub struct BackupInfo {
     path: String,
     created: String,
     size: u64,
+    verified: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupVerification {
+    Ok,
+    ChecksumMismatch,
+    Missing,
+}
+
+#[command]
+fn verify_backup(backup_path: String) -> Result<BackupVerification, String> {
+    let manifest_path = Path::new(&backup_path);
+    let manifest = match read_manifest(manifest_path) {
+        Some(manifest) => manifest,
+        None => return Ok(BackupVerification::Missing),
+    };
+    let chunks_dir = chunk_store_path(manifest_path.parent().unwrap_or(Path::new(".")));
+
+    match compute_stored_checksum(&manifest, &chunks_dir) {
+        Some(checksum) if checksum == manifest.checksum => Ok(BackupVerification::Ok),
+        Some(_) => Ok(BackupVerification::ChecksumMismatch),
+        None => Ok(BackupVerification::Missing),
+    }
 }
 
 #[command]
-fn restore_backup(backup_path: String, target_path: String) -> Result<(), String> {
-    if !Path::new(&backup_path).exists() {
+fn restore_backup(
+    backup_path: String,
+    target_path: String,
+    passphrase: Option<String>,
+    force: bool,
+) -> Result<(), String> {
+    let manifest_path = Path::new(&backup_path);
+    if !manifest_path.exists() {
         return Err("Backup file does not exist".to_string());
     }
-    
-    match fs::copy(&backup_path, &target_path) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Failed to restore backup: {}", e)),
+
+    let manifest = read_manifest(manifest_path)
+        .ok_or_else(|| "Failed to parse backup manifest".to_string())?;
+    let chunks_dir = chunk_store_path(manifest_path.parent().unwrap_or(Path::new(".")));
+
+    if !force {
+        match compute_stored_checksum(&manifest, &chunks_dir) {
+            Some(checksum) if checksum == manifest.checksum => {}
+            _ => {
+                return Err(
+                    "Backup failed integrity verification; pass force to restore anyway".to_string(),
+                )
+            }
+        }
     }
+
+    // Chunks in one manifest are usually encrypted under the same per-backup
+    // salt, so cache derived keys by salt instead of re-running the KDF for
+    // every chunk.
+    let mut key_cache: HashMap<Vec<u8>, [u8; ENCRYPTION_KEY_LEN]> = HashMap::new();
+
+    let mut plaintext = Vec::with_capacity(manifest.size as usize);
+    for hash in &manifest.chunks {
+        let stored = fs::read(chunk_path(&chunks_dir, hash))
+            .map_err(|e| format!("Missing backup chunk {}: {}", hash, e))?;
+        let chunk = if is_encrypted(&stored) {
+            let passphrase = passphrase.clone()
+                .ok_or_else(|| "This backup is encrypted; a passphrase is required".to_string())?;
+            let salt = encrypted_salt(&stored).unwrap().to_vec();
+            let key = cached_decryption_key(&mut key_cache, &salt, &passphrase)?;
+            open_with_key(&stored, &key)?
+        } else {
+            stored
+        };
+        plaintext.extend_from_slice(&chunk);
+    }
+
+    // The restored snapshot's own settings decide whether the live data file
+    // it's written to is encrypted at rest, in the format `save_data_file` writes.
+    let should_encrypt = serde_json::from_slice::<AppData>(&plaintext)
+        .map(|data| data.settings.encryption)
+        .unwrap_or(false);
+
+    let output = if should_encrypt {
+        let passphrase = passphrase
+            .ok_or_else(|| "Encryption is enabled but no passphrase was supplied".to_string())?;
+        encrypt_bytes(&plaintext, &passphrase)?
+    } else {
+        plaintext
+    };
+
+    fs::write(&target_path, output).map_err(|e| format!("Failed to restore backup: {}", e))
 }
 
 #[command]
 fn delete_backup(backup_path: String) -> Result<(), String> {
-    if !Path::new(&backup_path).exists() {
+    let path = Path::new(&backup_path);
+    if !path.exists() {
         return Err("Backup file does not exist".to_string());
     }
-    
-    match fs::remove_file(&backup_path) {
-        Ok(_) => Ok(()),
+
+    let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    let file_stem = backup_file_stem(file_name);
+    let directory = path.parent().map(|p| p.to_path_buf());
+
+    match fs::remove_file(path) {
+        Ok(_) => {
+            if let (Some(directory), Some(file_stem)) = (directory, file_stem) {
+                garbage_collect_chunks(&directory, &file_stem);
+                prune_generation_index(&directory, &file_stem);
+            }
+            Ok(())
+        }
         Err(e) => Err(format!("Failed to delete backup: {}", e)),
     }
 }
 
+#[command]
+fn list_generations(directory: String, file_stem: String) -> Result<Vec<GenerationRecord>, String> {
+    let index_path = generation_index_path(Path::new(&directory), &file_stem);
+    let mut records = read_generation_index(&index_path);
+    records.sort_by_key(|record| record.generation);
+    Ok(records)
+}
+
+// Reassembles and decrypts (if needed) the snapshot that `generation` recorded.
+fn load_generation_data(
+    directory: &Path,
+    records: &[GenerationRecord],
+    generation: u64,
+    passphrase: Option<&str>,
+) -> Result<AppData, String> {
+    let record = records
+        .iter()
+        .find(|record| record.generation == generation)
+        .ok_or_else(|| format!("Generation {} not found", generation))?;
+
+    let manifest_path = directory.join(&record.manifest);
+    let manifest = read_manifest(&manifest_path)
+        .ok_or_else(|| format!("Failed to read manifest for generation {}", generation))?;
+    let chunks_dir = chunk_store_path(directory);
+    let mut key_cache: HashMap<Vec<u8>, [u8; ENCRYPTION_KEY_LEN]> = HashMap::new();
+
+    let mut plaintext = Vec::with_capacity(manifest.size as usize);
+    for hash in &manifest.chunks {
+        let stored = fs::read(chunk_path(&chunks_dir, hash))
+            .map_err(|e| format!("Missing backup chunk {}: {}", hash, e))?;
+        let chunk = if is_encrypted(&stored) {
+            let passphrase = passphrase
+                .ok_or_else(|| "This backup is encrypted; a passphrase is required".to_string())?;
+            let salt = encrypted_salt(&stored).unwrap().to_vec();
+            let key = cached_decryption_key(&mut key_cache, &salt, passphrase)?;
+            open_with_key(&stored, &key)?
+        } else {
+            stored
+        };
+        plaintext.extend_from_slice(&chunk);
+    }
+
+    let content = String::from_utf8(plaintext)
+        .map_err(|e| format!("Decrypted content is not valid UTF-8: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse snapshot JSON: {}", e))
+}
+
+#[derive(Debug, Serialize)]
+pub struct HouseholdChange {
+    id: u32,
+    household_name: String,
+    segment_before: String,
+    segment_after: String,
+    next_review_due_before: String,
+    next_review_due_after: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GenerationDiff {
+    added: Vec<Household>,
+    removed: Vec<Household>,
+    changed: Vec<HouseholdChange>,
+}
+
+fn compute_generation_diff(households_a: &[Household], households_b: &[Household]) -> GenerationDiff {
+    let households_a: HashMap<u32, &Household> =
+        households_a.iter().map(|household| (household.id, household)).collect();
+    let households_b: HashMap<u32, &Household> =
+        households_b.iter().map(|household| (household.id, household)).collect();
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+
+    for (id, household_b) in &households_b {
+        match households_a.get(id) {
+            None => added.push((*household_b).clone()),
+            Some(household_a) => {
+                if household_a.segment != household_b.segment
+                    || household_a.next_review_due != household_b.next_review_due
+                {
+                    changed.push(HouseholdChange {
+                        id: *id,
+                        household_name: household_b.household_name.clone(),
+                        segment_before: household_a.segment.clone(),
+                        segment_after: household_b.segment.clone(),
+                        next_review_due_before: household_a.next_review_due.clone(),
+                        next_review_due_after: household_b.next_review_due.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    let removed = households_a
+        .iter()
+        .filter(|(id, _)| !households_b.contains_key(*id))
+        .map(|(_, household)| (*household).clone())
+        .collect();
+
+    GenerationDiff { added, removed, changed }
+}
+
+#[command]
+fn diff_generations(
+    directory: String,
+    file_stem: String,
+    generation_a: u64,
+    generation_b: u64,
+    passphrase: Option<String>,
+) -> Result<GenerationDiff, String> {
+    let dir_path = Path::new(&directory);
+    let index_path = generation_index_path(dir_path, &file_stem);
+    let records = read_generation_index(&index_path);
+
+    let data_a = load_generation_data(dir_path, &records, generation_a, passphrase.as_deref())?;
+    let data_b = load_generation_data(dir_path, &records, generation_b, passphrase.as_deref())?;
+
+    Ok(compute_generation_diff(&data_a.households, &data_b.households))
+}
+
 #[command]
 fn get_system_info() -> SystemInfo {
     SystemInfo {
@@ -320,13 +1082,17 @@ fn main() {
             save_data_file,
             create_backup,
             cleanup_old_backups,
+            prune_backups,
             get_app_version,
             validate_file_path,
             create_directory,
             get_file_info,
             export_backup_list,
+            verify_backup,
             restore_backup,
             delete_backup,
+            list_generations,
+            diff_generations,
             get_system_info
         ])
         .setup(|app| {
@@ -341,3 +1107,192 @@ fn main() {
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod diff_tests {
+    use super::*;
+
+    fn household(id: u32, segment: &str, next_review_due: &str) -> Household {
+        Household {
+            id,
+            household_name: format!("Household {}", id),
+            persons: Vec::new(),
+            next_review_due: next_review_due.to_string(),
+            review_type: "Periodic".to_string(),
+            auc: 0.0,
+            segment: segment.to_string(),
+            last_review_date: None,
+            review_status: "Scheduled".to_string(),
+            priority_flag: "Low".to_string(),
+            assigned_month: None,
+            created: "2024-01-01".to_string(),
+            updated: "2024-01-01".to_string(),
+        }
+    }
+
+    #[test]
+    fn detects_added_removed_and_changed_households() {
+        let a = vec![household(1, "Green", "2024-02-01"), household(2, "Red", "2024-03-01")];
+        let b = vec![household(1, "Yellow", "2024-02-01"), household(3, "Green", "2024-04-01")];
+
+        let diff = compute_generation_diff(&a, &b);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id, 3);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].id, 2);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].id, 1);
+        assert_eq!(diff.changed[0].segment_before, "Green");
+        assert_eq!(diff.changed[0].segment_after, "Yellow");
+    }
+
+    #[test]
+    fn identical_generations_have_no_diff() {
+        let a = vec![household(1, "Green", "2024-02-01")];
+        let b = a.clone();
+
+        let diff = compute_generation_diff(&a, &b);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(diff.changed.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod chunking_tests {
+    use super::*;
+
+    #[test]
+    fn chunks_reconstruct_original_data() {
+        let data: Vec<u8> = (0..500_000u32).map(|i| (i % 251) as u8).collect();
+        let chunks = content_defined_chunks(&data);
+        let reconstructed: Vec<u8> = chunks.iter().flat_map(|chunk| chunk.iter().copied()).collect();
+        assert_eq!(reconstructed, data);
+    }
+
+    #[test]
+    fn chunks_respect_max_size() {
+        let data = vec![0u8; 500_000];
+        for chunk in content_defined_chunks(&data) {
+            assert!(chunk.len() <= CHUNK_MAX_SIZE);
+        }
+    }
+
+    #[test]
+    fn mark_bucket_keeps_one_per_period() {
+        let base = NaiveDateTime::parse_from_str("2024-01-01_00-00-00", "%Y-%m-%d_%H-%M-%S").unwrap();
+        let backups = vec![
+            (PathBuf::from("a"), base),
+            (PathBuf::from("b"), base + chrono::Duration::days(1)),
+            (PathBuf::from("c"), base + chrono::Duration::days(32)),
+        ];
+        let mut keep = vec![false; backups.len()];
+        mark_bucket(&backups, &mut keep, 2, monthly_key);
+        assert_eq!(keep, vec![true, false, true]);
+    }
+
+    #[test]
+    fn mark_bucket_with_zero_count_keeps_nothing() {
+        let base = NaiveDateTime::parse_from_str("2024-01-01_00-00-00", "%Y-%m-%d_%H-%M-%S").unwrap();
+        let backups = vec![(PathBuf::from("a"), base)];
+        let mut keep = vec![false; backups.len()];
+        mark_bucket(&backups, &mut keep, 0, daily_key);
+        assert_eq!(keep, vec![false]);
+    }
+}
+
+#[cfg(test)]
+mod encryption_tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let plaintext = b"sensitive household data".to_vec();
+        let sealed = encrypt_bytes(&plaintext, "correct horse").unwrap();
+        assert!(is_encrypted(&sealed));
+        let opened = decrypt_bytes(&sealed, "correct horse").unwrap();
+        assert_eq!(opened, plaintext);
+    }
+
+    #[test]
+    fn decrypt_with_wrong_passphrase_fails() {
+        let sealed = encrypt_bytes(b"sensitive household data", "correct horse").unwrap();
+        let result = decrypt_bytes(&sealed, "wrong passphrase");
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod backup_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static SCRATCH_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn scratch_dir() -> PathBuf {
+        let n = SCRATCH_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("frdb_backup_test_{}_{}", std::process::id(), n));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn sample_data() -> AppData {
+        AppData {
+            households: vec![Household {
+                id: 1,
+                household_name: "Smith".to_string(),
+                persons: Vec::new(),
+                next_review_due: "2024-01-01".to_string(),
+                review_type: "Periodic".to_string(),
+                auc: 1.0,
+                segment: "Green".to_string(),
+                last_review_date: None,
+                review_status: "Scheduled".to_string(),
+                priority_flag: "Low".to_string(),
+                assigned_month: None,
+                created: "2024-01-01".to_string(),
+                updated: "2024-01-01".to_string(),
+            }],
+            settings: AppSettings {
+                last_file_path: None,
+                theme: "light".to_string(),
+                auto_backup: false,
+                backup_count: 1,
+                retention: RetentionPolicy {
+                    keep_last: 1,
+                    keep_daily: 0,
+                    keep_weekly: 0,
+                    keep_monthly: 0,
+                    keep_yearly: 0,
+                },
+                encryption: true,
+            },
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn reused_chunk_is_resealed_after_passphrase_rotation() {
+        let dir = scratch_dir();
+        let data_path = dir.join("data.json").to_string_lossy().to_string();
+
+        let backup_1 =
+            create_backup(data_path.clone(), sample_data(), true, Some("old-passphrase".to_string())).unwrap();
+        // Read before the second backup: both can land in the same second and share a manifest name.
+        let manifest_1 = read_manifest(Path::new(&backup_1)).unwrap();
+        let backup_2 =
+            create_backup(data_path, sample_data(), true, Some("new-passphrase".to_string())).unwrap();
+
+        let manifest_2 = read_manifest(Path::new(&backup_2)).unwrap();
+        assert_ne!(manifest_1.chunks, manifest_2.chunks, "rotated chunk must not reuse the old-passphrase copy");
+
+        let target_path = dir.join("restored.json").to_string_lossy().to_string();
+        restore_backup(backup_2, target_path.clone(), Some("new-passphrase".to_string()), true).unwrap();
+        let restored = fs::read(&target_path).unwrap();
+        decrypt_bytes(&restored, "new-passphrase").expect("restored backup must open with the current passphrase");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}